@@ -0,0 +1,92 @@
+use anyhow::Context;
+use brainflow::BoardIds;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Everything that used to be hard-coded constants: the board, the
+/// prediction/drone endpoints, and a `label -> DroneAction` table so
+/// predictions can be remapped to parameterized commands without
+/// recompiling. Label lookups are case-insensitive.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Config {
+    pub(crate) board: BoardConfig,
+    pub(crate) prediction_server_url: String,
+    pub(crate) drone_address: String,
+    pub(crate) commands: HashMap<String, DroneAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct BoardConfig {
+    pub(crate) id: String,
+    pub(crate) serial_port: String,
+    pub(crate) stream_seconds: u64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum DroneAction {
+    Takeoff,
+    Land,
+    Cw { deg: u32 },
+    Ccw { deg: u32 },
+    Forward { cm: u32 },
+    Back { cm: u32 },
+}
+
+impl Config {
+    pub(crate) fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut config: Config = toml::from_str(&contents).context("invalid config.toml")?;
+        // Normalize here, not just on the lookup side, so a table written as
+        // `[commands.Takeoff]` still matches a "takeoff"/"Takeoff" label.
+        config.commands = config
+            .commands
+            .into_iter()
+            .map(|(label, action)| (label.to_lowercase(), action))
+            .collect();
+        Ok(config)
+    }
+
+    /// Case-insensitive label lookup into the configured command table.
+    pub(crate) fn action_for(&self, label: &str) -> Option<DroneAction> {
+        self.commands.get(&label.to_lowercase()).copied()
+    }
+}
+
+impl BoardConfig {
+    pub(crate) fn board_id(&self) -> anyhow::Result<BoardIds> {
+        match self.id.as_str() {
+            "CytonDaisyBoard" => Ok(BoardIds::CytonDaisyBoard),
+            "CytonBoard" => Ok(BoardIds::CytonBoard),
+            "SyntheticBoard" => Ok(BoardIds::SyntheticBoard),
+            other => anyhow::bail!("unknown board id in config.toml: {other}"),
+        }
+    }
+}
+
+impl Default for Config {
+    /// Mirrors the previous hard-coded constants and `Movements` match, so
+    /// the app keeps working out of the box without a `config.toml`.
+    fn default() -> Self {
+        let commands = HashMap::from([
+            ("takeoff".to_string(), DroneAction::Takeoff),
+            ("land".to_string(), DroneAction::Land),
+            ("right".to_string(), DroneAction::Cw { deg: 90 }),
+            ("left".to_string(), DroneAction::Ccw { deg: 90 }),
+            ("forward".to_string(), DroneAction::Forward { cm: 100 }),
+            ("backward".to_string(), DroneAction::Back { cm: 100 }),
+        ]);
+
+        Config {
+            board: BoardConfig {
+                id: "CytonDaisyBoard".to_string(),
+                serial_port: "/dev/ttyUSB0".to_string(),
+                stream_seconds: 10,
+            },
+            prediction_server_url: "http://127.0.0.1:5000/prediction".to_string(),
+            drone_address: "192.168.10.1:8889".to_string(),
+            commands,
+        }
+    }
+}
@@ -6,223 +6,391 @@ use iced::widget::{
 use iced::{
     alignment::{Horizontal, Vertical},
     border::Radius,
-    Background, Border, Color, Element, Length, Sandbox, Settings, Shadow,
+    executor, Application, Background, Border, Color, Command, Element, Length, Settings, Shadow,
 };
 use once_cell::sync::Lazy;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tello::CommandMode;
 use tokio::runtime::Runtime;
+use tokio::sync::{Mutex as AsyncMutex, OnceCell};
 
-static DRONE: Lazy<Arc<Mutex<CommandMode>>> = Lazy::new(|| {
-    let rt = Runtime::new().unwrap();
-    let drone = rt.block_on(CommandMode::new("192.168.10.1:8889")).unwrap();
-    Arc::new(Mutex::new(drone))
+mod config;
+mod storage;
+mod telemetry;
+
+use config::{Config, DroneAction};
+use storage::{JsonlStore, SessionRecord, SessionStore, SqliteStore};
+use telemetry::TelemetryState;
+
+static CONFIG: Lazy<Config> = Lazy::new(|| {
+    Config::load("config.toml").unwrap_or_else(|err| {
+        eprintln!("using built-in config ({err})");
+        Config::default()
+    })
 });
 
-#[derive(Debug, Deserialize, Serialize)]
-struct Prediction {
-    prediction_label: String,
-    prediction_count: usize,
-}
+static STORE: Lazy<Box<dyn SessionStore>> = Lazy::new(|| match SqliteStore::open("sessions.db") {
+    Ok(store) => Box::new(store),
+    Err(err) => {
+        eprintln!("falling back to JSON-lines session store: {err}");
+        Box::new(JsonlStore::new("sessions"))
+    }
+});
 
-#[derive(Debug, Copy, Clone)]
-enum Movements {
-    Takeoff,
-    Right,
-    Left,
-    Land,
-    Forward,
-    Backward,
-    None,
+// Shared by the async `Command`s and subscriptions for their blocking work
+// (board reads and session storage, via `spawn_blocking`), so it runs off the
+// UI thread instead of each read spinning up (and tearing down) its own
+// runtime.
+static RUNTIME: Lazy<Runtime> =
+    Lazy::new(|| Runtime::new().expect("failed to start shared tokio runtime"));
+
+// Connecting is itself async, so this can only be initialized from inside an
+// async context (the `Command`/subscription futures iced's Tokio-backed
+// executor is already driving) — a `Lazy` that called `block_on` to connect
+// would panic the first time it was forced from one of those futures.
+static DRONE: OnceCell<Arc<AsyncMutex<CommandMode>>> = OnceCell::const_new();
+
+async fn drone_handle() -> Result<Arc<AsyncMutex<CommandMode>>, String> {
+    DRONE
+        .get_or_try_init(|| async {
+            CommandMode::new(CONFIG.drone_address.as_str())
+                .await
+                .map(|drone| Arc::new(AsyncMutex::new(drone)))
+        })
+        .await
+        .map(Arc::clone)
+        .map_err(|e| format!("{e:?}"))
 }
 
-impl From<&str> for Movements {
-    fn from(value: &str) -> Self {
-        match value {
-            "takeoff" => Movements::Takeoff,
-            "right" => Movements::Right,
-            "left" => Movements::Left,
-            "land" => Movements::Land,
-            "forward" => Movements::Forward,
-            "backward" => Movements::Backward,
-            "Takeoff" => Movements::Takeoff,
-            "Right" => Movements::Right,
-            "Left" => Movements::Left,
-            "Land" => Movements::Land,
-            "Forward" => Movements::Forward,
-            "Backward" => Movements::Backward,
-            _ => Movements::None,
-        }
-    }
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct Prediction {
+    pub(crate) prediction_label: String,
+    pub(crate) prediction_count: usize,
 }
 
 fn main() -> Result<(), iced::Error> {
     PredictionWindow::run(Settings::default())
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct PredictionWindow {
     movement: String,
     reading_counter: usize,
     history: Vec<Prediction>,
     connection: bool,
+    busy: bool,
+    streaming: bool,
+    // Sliding-window streaming + majority-vote smoothing, so a single noisy
+    // prediction can't command a real takeoff/land.
+    window: Duration,
+    stride: Duration,
+    vote_window: usize,
+    vote_threshold: usize,
+    cooldown: Duration,
+    label_buffer: VecDeque<String>,
+    last_dispatch: Option<Instant>,
+    session_id: String,
+    replay_queue: Option<VecDeque<SessionRecord>>,
+    telemetry: Option<TelemetryState>,
+    battery_threshold: u8,
+    telemetry_timeout: Duration,
+    // Set on every `Err(...)` arm and cleared on success, so a failure is
+    // visible in the GUI instead of only reaching the terminal via
+    // `eprintln!`.
+    last_error: Option<String>,
+}
+
+impl Default for PredictionWindow {
+    fn default() -> Self {
+        let session_id = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis().to_string())
+            .unwrap_or_else(|_| "0".to_string());
+
+        let vote_window = 5;
+        let vote_threshold = 3;
+        // A real (not debug-only) check: this must hold for `majority_label`'s
+        // first-seen-order tie-break to pick a unique winner, and this code
+        // feeds real takeoff/land/forward commands — it must not ship broken
+        // in a release build.
+        assert!(
+            vote_threshold > vote_window / 2,
+            "vote_threshold must be a strict majority of vote_window to guarantee a unique winner"
+        );
+
+        PredictionWindow {
+            movement: String::new(),
+            reading_counter: 0,
+            history: Vec::new(),
+            connection: false,
+            busy: false,
+            streaming: false,
+            window: Duration::from_secs(2),
+            stride: Duration::from_millis(500),
+            vote_window,
+            vote_threshold,
+            cooldown: Duration::from_millis(3000),
+            label_buffer: VecDeque::new(),
+            last_dispatch: None,
+            session_id,
+            replay_queue: None,
+            telemetry: None,
+            battery_threshold: 20,
+            telemetry_timeout: Duration::from_secs(5),
+            last_error: None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 enum Message {
     ReadBrain,
+    ReadComplete(Result<Prediction, String>),
     Connect,
+    ConnectComplete(Result<(), String>),
     Execute,
     Takeoff,
     Land,
+    CommandComplete(Result<(), String>),
+    ToggleStreaming,
+    StreamPrediction(Result<Prediction, String>),
+    StartReplay,
+    ReplayLoaded(Result<Vec<SessionRecord>, String>),
+    ReplayTick,
+    TelemetryUpdate(Result<TelemetryState, String>),
+}
+
+impl PredictionWindow {
+    /// True once telemetry has arrived recently and the battery clears the
+    /// configured threshold, so flight commands aren't dispatched to a
+    /// drone that's gone quiet or is about to die mid-air.
+    fn drone_responsive(&self) -> bool {
+        self.telemetry.as_ref().is_some_and(|telemetry| {
+            !telemetry.is_stale(self.telemetry_timeout)
+                && telemetry.battery_percent.unwrap_or(0) >= self.battery_threshold
+        })
+    }
 }
 
-impl Sandbox for PredictionWindow {
+impl Application for PredictionWindow {
+    type Executor = executor::Default;
     type Message = Message;
+    type Theme = iced::Theme;
+    type Flags = ();
 
-    fn new() -> Self {
-        PredictionWindow {
-            movement: String::new(),
-            reading_counter: 0,
-            history: Vec::new(),
-            connection: false,
-        }
+    fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
+        (PredictionWindow::default(), Command::none())
     }
 
     fn title(&self) -> String {
         String::from("Brain Reader page")
     }
-    fn update(&mut self, message: Self::Message) {
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
         match message {
             Message::ReadBrain => {
-                let readings = read_cyton_board();
-                let Ok(readings) = readings else {
-                    eprintln!("{:?}", readings);
-                    return;
-                };
-                let Ok(rt) = Runtime::new() else {
-                    eprintln!("could not generate run time");
-                    return;
-                };
-
-                let url = "http://127.0.0.1:5000/prediction";
-                let json = rt
-                    .block_on(Client::post(&Client::new(), url).json(&readings).send())
-                    .and_then(|response| rt.block_on(response.json::<Prediction>()));
-
-                let Ok(json) = json else {
-                    eprintln!("{:?}", json);
-                    return;
-                };
-
-                self.movement = json.prediction_label.clone();
-                self.reading_counter = json.prediction_count;
-                self.history.push(json);
+                if self.busy {
+                    return Command::none();
+                }
+                self.busy = true;
+                Command::perform(
+                    read_brain_and_predict(self.session_id.clone()),
+                    Message::ReadComplete,
+                )
+            }
+            Message::ReadComplete(result) => {
+                self.busy = false;
+                match result {
+                    Ok(prediction) => {
+                        self.last_error = None;
+                        self.movement = prediction.prediction_label.clone();
+                        self.reading_counter = prediction.prediction_count;
+                        self.history.push(prediction);
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        self.last_error = Some(err);
+                    }
+                }
+                Command::none()
             }
             Message::Connect => {
-                if self.connection {
-                    return;
+                if self.connection || self.busy {
+                    return Command::none();
                 }
-                let Ok(drone) = DRONE.try_lock() else {
-                    eprintln!("Unable to obtain a lock on the drone");
-                    return;
-                };
-                let Ok(rt) = Runtime::new() else {
-                    eprintln!("unable to bind runtime");
-                    return;
-                };
-                let res = rt.block_on(drone.enable());
-                let Ok(_) = res else {
-                    eprintln!("{:?}", res);
-                    return;
-                };
-                self.connection = true;
+                self.busy = true;
+                Command::perform(
+                    async {
+                        let drone = drone_handle().await?;
+                        let drone = drone
+                            .try_lock()
+                            .map_err(|_| "Unable to obtain a lock on the drone".to_string())?;
+                        drone.enable().await.map_err(|e| format!("{e:?}"))
+                    },
+                    Message::ConnectComplete,
+                )
+            }
+            Message::ConnectComplete(result) => {
+                self.busy = false;
+                match result {
+                    Ok(()) => {
+                        self.connection = true;
+                        self.last_error = None;
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        self.last_error = Some(err);
+                    }
+                }
+                Command::none()
             }
             Message::Execute => {
-                if !self.connection {
-                    return;
+                if !self.connection || self.busy || !self.drone_responsive() {
+                    return Command::none();
                 }
-                let movement = self.movement.clone();
-
-                let Ok(mut drone) = DRONE.try_lock() else {
-                    eprintln!("Unable to obtain a lock on the drone");
-                    return;
-                };
-
-                let Ok(rt) = Runtime::new() else {
-                    eprintln!("unable to bind runtime");
-                    return;
+                let Some(action) = CONFIG.action_for(&self.movement) else {
+                    let err = format!("no command mapped for label \"{}\"", self.movement);
+                    eprintln!("{err}");
+                    self.last_error = Some(err);
+                    return Command::none();
                 };
-                let _ = match Movements::from(movement.as_str()) {
-                    Movements::Takeoff => rt.block_on(drone.take_off()),
-                    Movements::Land => rt.block_on(drone.land()),
-                    Movements::Right => rt.block_on(drone.cw(90)),
-                    Movements::Left => rt.block_on(drone.ccw(90)),
-                    Movements::Forward => rt.block_on(drone.forward(100)),
-                    Movements::Backward => rt.block_on(drone.back(100)),
-                    Movements::None => Ok(()),
-                };
-
-                // fire and forget method may or may not work didn't feel like finding out
-                /* let Ok(rt) = Runtime::new() else {
-                    eprintln!("unable to bind runtime");
-                    return;
-                };
-                rt.spawn(async move {
-                    let Ok(mut drone) = DRONE.try_lock() else {
-                        eprintln!("Unable to obtain a lock on the drone");
-                        return;
-                    };
-
-                    let Ok(rt) = Runtime::new() else {
-                        eprintln!("unable to bind runtime");
-                        return;
-                    };
-                    let _ = match Movements::from(movement.as_str()) {
-                        Movements::Takeoff => rt.block_on(drone.take_off()),
-                        Movements::Land => rt.block_on(drone.land()),
-                        Movements::Right => rt.block_on(drone.cw(90)),
-                        Movements::Left => rt.block_on(drone.ccw(90)),
-                        Movements::Forward => rt.block_on(drone.forward(100)),
-                        Movements::Backward => rt.block_on(drone.back(100)),
-                        Movements::None => Ok(()),
-                    };
-                }); */
+                self.busy = true;
+                Command::perform(run_action(action), Message::CommandComplete)
             }
             Message::Takeoff => {
-                if !self.connection {
-                    return;
+                if !self.connection || self.busy || !self.drone_responsive() {
+                    return Command::none();
                 }
-                let Ok(mut drone) = DRONE.try_lock() else {
-                    eprintln!("Unable to obtain a lock on the drone");
-                    return;
+                self.busy = true;
+                Command::perform(run_action(DroneAction::Takeoff), Message::CommandComplete)
+            }
+            Message::Land => {
+                if !self.connection || self.busy {
+                    return Command::none();
+                }
+                self.busy = true;
+                Command::perform(run_action(DroneAction::Land), Message::CommandComplete)
+            }
+            Message::CommandComplete(result) => {
+                self.busy = false;
+                match result {
+                    Ok(()) => self.last_error = None,
+                    Err(err) => {
+                        eprintln!("{err}");
+                        self.last_error = Some(err);
+                    }
+                }
+                Command::none()
+            }
+            Message::ToggleStreaming => {
+                self.streaming = !self.streaming;
+                self.label_buffer.clear();
+                self.last_dispatch = None;
+                Command::none()
+            }
+            Message::StreamPrediction(result) => {
+                let prediction = match result {
+                    Ok(prediction) => {
+                        self.last_error = None;
+                        prediction
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        self.last_error = Some(err);
+                        return Command::none();
+                    }
                 };
 
-                let Ok(rt) = Runtime::new() else {
-                    eprintln!("unable to bind runtime");
-                    return;
+                self.movement = prediction.prediction_label.clone();
+                self.reading_counter = prediction.prediction_count;
+                let label = prediction.prediction_label.clone();
+                self.history.push(prediction);
+
+                self.label_buffer.push_back(label);
+                while self.label_buffer.len() > self.vote_window {
+                    self.label_buffer.pop_front();
+                }
+
+                if self.busy || self.label_buffer.len() < self.vote_window {
+                    return Command::none();
+                }
+                if let Some(last) = self.last_dispatch {
+                    if last.elapsed() < self.cooldown {
+                        return Command::none();
+                    }
+                }
+
+                let Some(winner) = majority_label(&self.label_buffer, self.vote_threshold) else {
+                    return Command::none();
                 };
-                let _ = rt.block_on(drone.take_off());
+                let Some(action) = CONFIG.action_for(&winner) else {
+                    let err = format!("no command mapped for label \"{winner}\"");
+                    eprintln!("{err}");
+                    self.last_error = Some(err);
+                    return Command::none();
+                };
+                if !self.drone_responsive() {
+                    let err = format!("skipping \"{winner}\" — battery low or telemetry stale");
+                    eprintln!("{err}");
+                    self.last_error = Some(err);
+                    return Command::none();
+                }
+
+                self.label_buffer.clear();
+                self.last_dispatch = Some(Instant::now());
+                self.busy = true;
+                Command::perform(run_action(action), Message::CommandComplete)
             }
-            Message::Land => {
-                if !self.connection {
-                    return;
+            Message::StartReplay => {
+                if self.replay_queue.is_some() {
+                    return Command::none();
                 }
-                let Ok(drone) = DRONE.try_lock() else {
-                    eprintln!("Unable to obtain a lock on the drone");
-                    return;
+                Command::perform(load_last_session(), Message::ReplayLoaded)
+            }
+            Message::ReplayLoaded(result) => {
+                match result {
+                    Ok(records) => {
+                        self.replay_queue = Some(records.into_iter().collect());
+                        self.last_error = None;
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        self.last_error = Some(err);
+                    }
+                }
+                Command::none()
+            }
+            Message::ReplayTick => {
+                let Some(queue) = self.replay_queue.as_mut() else {
+                    return Command::none();
+                };
+                let Some(record) = queue.pop_front() else {
+                    self.replay_queue = None;
+                    return Command::none();
                 };
 
-                let Ok(rt) = Runtime::new() else {
-                    eprintln!("unable to bind runtime");
-                    return;
+                let prediction = Prediction {
+                    prediction_label: record.prediction_label,
+                    prediction_count: record.prediction_count,
                 };
-                let _ = rt.block_on(drone.land());
+                self.update(Message::StreamPrediction(Ok(prediction)))
+            }
+            Message::TelemetryUpdate(result) => {
+                match result {
+                    Ok(state) => {
+                        self.telemetry = Some(state);
+                        self.last_error = None;
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        self.last_error = Some(err);
+                    }
+                }
+                Command::none()
             }
         }
     }
@@ -233,7 +401,7 @@ impl Sandbox for PredictionWindow {
                 .horizontal_alignment(Horizontal::Center)
                 .vertical_alignment(Vertical::Center),
         )
-        .on_press(Message::ReadBrain)
+        .on_press_maybe((!self.busy).then_some(Message::ReadBrain))
         .width(200)
         .height(75);
         let execute = button(
@@ -241,7 +409,29 @@ impl Sandbox for PredictionWindow {
                 .horizontal_alignment(Horizontal::Center)
                 .vertical_alignment(Vertical::Center),
         )
-        .on_press(Message::Execute)
+        .on_press_maybe(
+            (self.connection && !self.busy && self.drone_responsive()).then_some(Message::Execute),
+        )
+        .width(200)
+        .height(50);
+        let toggle_stream = button(
+            text(if self.streaming {
+                "Stop Live Stream"
+            } else {
+                "Start Live Stream"
+            })
+            .horizontal_alignment(Horizontal::Center)
+            .vertical_alignment(Vertical::Center),
+        )
+        .on_press(Message::ToggleStreaming)
+        .width(200)
+        .height(50);
+        let replay = button(
+            text("Replay Last Session")
+                .horizontal_alignment(Horizontal::Center)
+                .vertical_alignment(Vertical::Center),
+        )
+        .on_press_maybe((self.replay_queue.is_none()).then_some(Message::StartReplay))
         .width(200)
         .height(50);
         let movement_prediction = apply_black_boarder(
@@ -278,6 +468,8 @@ impl Sandbox for PredictionWindow {
                     row![counter, movement_prediction]
                 ],
                 execute,
+                toggle_stream,
+                replay,
             ]
             .spacing(10),
         )
@@ -312,7 +504,7 @@ impl Sandbox for PredictionWindow {
                 .horizontal_alignment(Horizontal::Center)
                 .vertical_alignment(Vertical::Center),
         )
-        .on_press(Message::Connect)
+        .on_press_maybe((!self.connection && !self.busy).then_some(Message::Connect))
         .width(100)
         .height(100);
 
@@ -321,7 +513,9 @@ impl Sandbox for PredictionWindow {
                 .horizontal_alignment(Horizontal::Center)
                 .vertical_alignment(Vertical::Center),
         )
-        .on_press(Message::Takeoff)
+        .on_press_maybe(
+            (self.connection && !self.busy && self.drone_responsive()).then_some(Message::Takeoff),
+        )
         .width(50)
         .height(50);
         let land = button(
@@ -329,10 +523,25 @@ impl Sandbox for PredictionWindow {
                 .horizontal_alignment(Horizontal::Center)
                 .vertical_alignment(Vertical::Center),
         )
-        .on_press(Message::Land)
+        .on_press_maybe((self.connection && !self.busy).then_some(Message::Land))
         .width(50)
         .height(50);
 
+        let status_panel = apply_black_boarder(
+            column![
+                text(telemetry_summary_line(&self.telemetry)),
+                text(flight_detail_line(&self.telemetry)),
+                text(battery_warning_line(
+                    &self.telemetry,
+                    self.battery_threshold,
+                    self.telemetry_timeout
+                )),
+                text(self.last_error.clone().unwrap_or_default()),
+            ]
+            .spacing(5),
+        )
+        .width(200);
+
         let view = row![
             Space::with_width(Length::FillPortion(1)),
             column![
@@ -340,6 +549,7 @@ impl Sandbox for PredictionWindow {
                 connect,
                 Space::with_width(100),
                 row![takeoff, land],
+                status_panel,
                 Space::with_height(Length::FillPortion(1))
             ]
             .width(Length::FillPortion(1)),
@@ -359,6 +569,46 @@ impl Sandbox for PredictionWindow {
     fn theme(&self) -> iced::Theme {
         iced::Theme::Dark
     }
+
+    fn subscription(&self) -> iced::Subscription<Self::Message> {
+        let mut subscriptions = Vec::new();
+        if self.streaming {
+            subscriptions.push(streaming_subscription(
+                self.window,
+                self.stride,
+                self.session_id.clone(),
+            ));
+        }
+        if self.replay_queue.is_some() {
+            subscriptions.push(replay_tick_subscription(self.stride));
+        }
+        if self.connection {
+            subscriptions.push(telemetry::telemetry_subscription());
+        }
+        iced::Subscription::batch(subscriptions)
+    }
+}
+
+/// Counts labels in `buffer` and returns the one holding a majority (at
+/// least `threshold` occurrences), if any.
+/// Tallies in first-seen order (a `Vec`, not a `HashMap`) so that if more
+/// than one label could reach `threshold` in the same window the result is
+/// deterministic rather than depending on hash-map iteration order — this
+/// feeds a real flight command, so it must not be a coin flip. Callers are
+/// expected to have validated `threshold > window / 2` (see the `assert!` in
+/// `PredictionWindow::default`) so that at most one label can ever qualify.
+fn majority_label(buffer: &VecDeque<String>, threshold: usize) -> Option<String> {
+    let mut tally: Vec<(&str, usize)> = Vec::new();
+    for label in buffer {
+        match tally.iter_mut().find(|(seen, _)| *seen == label.as_str()) {
+            Some((_, count)) => *count += 1,
+            None => tally.push((label.as_str(), 1)),
+        }
+    }
+    tally
+        .into_iter()
+        .find(|(_, count)| *count >= threshold)
+        .map(|(label, _)| label.to_string())
 }
 
 fn apply_black_boarder<'a>(
@@ -393,15 +643,266 @@ fn display_count(history: &Vec<Prediction>) -> String {
     counts
 }
 
+fn telemetry_summary_line(telemetry: &Option<TelemetryState>) -> String {
+    let Some(telemetry) = telemetry else {
+        return "battery: --  height: --  tof: --".to_string();
+    };
+
+    let opt = |v: Option<i32>| v.map_or("--".to_string(), |v| v.to_string());
+
+    format!(
+        "battery: {}%  height: {}cm  tof: {}cm",
+        telemetry
+            .battery_percent
+            .map_or("--".to_string(), |b| b.to_string()),
+        opt(telemetry.height_cm),
+        opt(telemetry.time_of_flight_cm),
+    )
+}
+
+fn flight_detail_line(telemetry: &Option<TelemetryState>) -> String {
+    let Some(telemetry) = telemetry else {
+        return "pitch: --  roll: --  yaw: --  temp: --  flight time: --".to_string();
+    };
+
+    let opt = |v: Option<i32>| v.map_or("--".to_string(), |v| v.to_string());
+
+    format!(
+        "pitch: {}°  roll: {}°  yaw: {}°  temp: {}-{}°C  flight time: {}s",
+        opt(telemetry.pitch_deg),
+        opt(telemetry.roll_deg),
+        opt(telemetry.yaw_deg),
+        opt(telemetry.temperature_low_c),
+        opt(telemetry.temperature_high_c),
+        opt(telemetry.flight_time_s),
+    )
+}
+
+fn battery_warning_line(
+    telemetry: &Option<TelemetryState>,
+    battery_threshold: u8,
+    telemetry_timeout: Duration,
+) -> String {
+    match telemetry {
+        None => "no telemetry yet".to_string(),
+        Some(telemetry) if telemetry.is_stale(telemetry_timeout) => {
+            "telemetry stale — commands disabled".to_string()
+        }
+        Some(telemetry) if telemetry.battery_percent.unwrap_or(0) < battery_threshold => {
+            "LOW BATTERY — commands disabled".to_string()
+        }
+        Some(_) => "drone responsive".to_string(),
+    }
+}
+
+async fn read_brain_and_predict(session_id: String) -> Result<Prediction, String> {
+    let readings = RUNTIME
+        .spawn_blocking(read_cyton_board)
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let prediction = Client::new()
+        .post(&CONFIG.prediction_server_url)
+        .json(&readings)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .json::<Prediction>()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    record_session(session_id, &prediction, readings).await;
+    Ok(prediction)
+}
+
+/// Best-effort: a storage failure shouldn't stop a reading from reaching the
+/// GUI or a flight command from going out.
+async fn record_session(
+    session_id: String,
+    prediction: &Prediction,
+    readings: HashMap<String, Vec<f64>>,
+) {
+    let record = SessionRecord::new(
+        prediction,
+        readings,
+        CONFIG.board.id.as_str(),
+        CONFIG.board.serial_port.as_str(),
+    );
+    let result = RUNTIME
+        .spawn_blocking(move || STORE.record(&session_id, &record))
+        .await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => eprintln!("failed to record session: {err}"),
+        Err(err) => eprintln!("failed to record session: {err}"),
+    }
+}
+
+async fn load_last_session() -> Result<Vec<SessionRecord>, String> {
+    RUNTIME
+        .spawn_blocking(|| {
+            let sessions = STORE.list_sessions().map_err(|e| e.to_string())?;
+            let last = sessions
+                .last()
+                .ok_or_else(|| "no recorded sessions to replay".to_string())?;
+            STORE.load_session(last).map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+async fn run_action(action: DroneAction) -> Result<(), String> {
+    let drone = drone_handle().await?;
+    let mut drone = drone
+        .try_lock()
+        .map_err(|_| "Unable to obtain a lock on the drone".to_string())?;
+
+    match action {
+        DroneAction::Takeoff => drone.take_off().await,
+        DroneAction::Land => drone.land().await,
+        DroneAction::Cw { deg } => drone.cw(deg).await,
+        DroneAction::Ccw { deg } => drone.ccw(deg).await,
+        DroneAction::Forward { cm } => drone.forward(cm).await,
+        DroneAction::Back { cm } => drone.back(cm).await,
+    }
+    .map_err(|e| format!("{e:?}"))
+}
+
+// The Cyton+Daisy pair reports a shared 125Hz sample rate, used to size the
+// sliding window pulled from the board's ring buffer on every stride tick.
+const SAMPLE_RATE_HZ: f64 = 125.0;
+
+enum StreamState {
+    Idle,
+    Streaming(board_shim::BoardShim),
+}
+
+/// Subscribes to a continuous Cyton stream, slicing a `window`-long batch of
+/// samples every `stride` and running it through the prediction server.
+fn streaming_subscription(
+    window: Duration,
+    stride: Duration,
+    session_id: String,
+) -> iced::Subscription<Message> {
+    iced::subscription::unfold("brain-stream", StreamState::Idle, move |state| {
+        let session_id = session_id.clone();
+        async move {
+            let board = match state {
+                StreamState::Idle => match RUNTIME.spawn_blocking(prepare_streaming_board).await {
+                    Ok(Ok(board)) => board,
+                    Ok(Err(err)) => {
+                        return (
+                            Message::StreamPrediction(Err(err.to_string())),
+                            StreamState::Idle,
+                        )
+                    }
+                    Err(err) => {
+                        return (
+                            Message::StreamPrediction(Err(err.to_string())),
+                            StreamState::Idle,
+                        )
+                    }
+                },
+                StreamState::Streaming(board) => board,
+            };
+
+            tokio::time::sleep(stride).await;
+
+            let num_samples = (window.as_secs_f64() * SAMPLE_RATE_HZ).ceil() as usize;
+            let (board, readings) = match RUNTIME
+                .spawn_blocking(move || {
+                    let readings = read_window(&board, num_samples);
+                    (board, readings)
+                })
+                .await
+            {
+                Ok((board, readings)) => (board, readings),
+                Err(err) => {
+                    return (
+                        Message::StreamPrediction(Err(err.to_string())),
+                        StreamState::Idle,
+                    )
+                }
+            };
+
+            let readings = match readings {
+                Ok(readings) => readings,
+                Err(err) => {
+                    return (
+                        Message::StreamPrediction(Err(err.to_string())),
+                        StreamState::Streaming(board),
+                    )
+                }
+            };
+
+            let prediction = Client::new()
+                .post(&CONFIG.prediction_server_url)
+                .json(&readings)
+                .send()
+                .await
+                .map_err(|e| e.to_string());
+            let prediction = match prediction {
+                Ok(response) => response
+                    .json::<Prediction>()
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(err) => Err(err),
+            };
+
+            if let Ok(prediction) = &prediction {
+                record_session(session_id, prediction, readings).await;
+            }
+
+            (
+                Message::StreamPrediction(prediction),
+                StreamState::Streaming(board),
+            )
+        }
+    })
+}
+
+/// Ticks every `stride` while a session is queued for replay; `update`
+/// drains `replay_queue` one record at a time on each tick.
+fn replay_tick_subscription(stride: Duration) -> iced::Subscription<Message> {
+    iced::subscription::unfold("brain-replay", (), move |_| async move {
+        tokio::time::sleep(stride).await;
+        (Message::ReplayTick, ())
+    })
+}
+
+fn prepare_streaming_board() -> anyhow::Result<board_shim::BoardShim> {
+    let params = BrainFlowInputParamsBuilder::default()
+        .serial_port(CONFIG.board.serial_port.as_str())
+        .build();
+    let board = board_shim::BoardShim::new(CONFIG.board.board_id()?, params)?;
+    board.prepare_session()?;
+    board.start_stream(45000, "")?;
+    Ok(board)
+}
+
+fn read_window(
+    board: &board_shim::BoardShim,
+    num_samples: usize,
+) -> anyhow::Result<HashMap<String, Vec<f64>>> {
+    let data = board.get_current_board_data(num_samples, BrainFlowPresets::DefaultPreset)?;
+
+    let mut readings = HashMap::new();
+    for (i, arr) in data.rows().into_iter().enumerate() {
+        readings.insert(format!("c{}", i), arr.into_owned().to_vec());
+    }
+    Ok(readings)
+}
+
 fn read_cyton_board() -> anyhow::Result<HashMap<String, Vec<f64>>> {
     let params = BrainFlowInputParamsBuilder::default()
-        .serial_port("/dev/ttyUSB0")
+        .serial_port(CONFIG.board.serial_port.as_str())
         .build();
-    let board = board_shim::BoardShim::new(BoardIds::CytonDaisyBoard, params)?;
+    let board = board_shim::BoardShim::new(CONFIG.board.board_id()?, params)?;
     board.prepare_session()?;
 
     board.start_stream(45000, "")?;
-    thread::sleep(Duration::from_secs(10));
+    thread::sleep(Duration::from_secs(CONFIG.board.stream_seconds));
 
     board.stop_stream()?;
     let data = board.get_board_data(None, BrainFlowPresets::DefaultPreset)?;
@@ -0,0 +1,186 @@
+use crate::Prediction;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One recorded `ReadBrain`/prediction event: the raw readings that went
+/// into the prediction plus the label and metadata the prediction came back
+/// with, so a session can be replayed without the Cyton board attached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SessionRecord {
+    pub(crate) timestamp_ms: i64,
+    pub(crate) board_id: String,
+    pub(crate) serial_port: String,
+    pub(crate) prediction_label: String,
+    pub(crate) prediction_count: usize,
+    pub(crate) readings: HashMap<String, Vec<f64>>,
+}
+
+impl SessionRecord {
+    pub(crate) fn new(
+        prediction: &Prediction,
+        readings: HashMap<String, Vec<f64>>,
+        board_id: impl Into<String>,
+        serial_port: impl Into<String>,
+    ) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        SessionRecord {
+            timestamp_ms,
+            board_id: board_id.into(),
+            serial_port: serial_port.into(),
+            prediction_label: prediction.prediction_label.clone(),
+            prediction_count: prediction.prediction_count,
+            readings,
+        }
+    }
+}
+
+/// Pluggable persistence for recorded sessions. `record` is append-only;
+/// `load_session`/`list_sessions` back the `Replay` mode.
+pub(crate) trait SessionStore: Send + Sync {
+    fn record(&self, session_id: &str, record: &SessionRecord) -> anyhow::Result<()>;
+    fn load_session(&self, session_id: &str) -> anyhow::Result<Vec<SessionRecord>>;
+    fn list_sessions(&self) -> anyhow::Result<Vec<String>>;
+}
+
+pub(crate) struct SqliteStore {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+impl SqliteStore {
+    pub(crate) fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let conn = rusqlite::Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS readings (
+                session_id       TEXT NOT NULL,
+                timestamp_ms     INTEGER NOT NULL,
+                board_id         TEXT NOT NULL,
+                serial_port      TEXT NOT NULL,
+                prediction_label TEXT NOT NULL,
+                prediction_count INTEGER NOT NULL,
+                readings_json    TEXT NOT NULL
+            );",
+        )?;
+        Ok(SqliteStore {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> anyhow::Result<std::sync::MutexGuard<'_, rusqlite::Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| anyhow::anyhow!("sqlite connection mutex was poisoned"))
+    }
+}
+
+impl SessionStore for SqliteStore {
+    fn record(&self, session_id: &str, record: &SessionRecord) -> anyhow::Result<()> {
+        self.conn()?.execute(
+            "INSERT INTO readings (
+                session_id, timestamp_ms, board_id, serial_port,
+                prediction_label, prediction_count, readings_json
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                session_id,
+                record.timestamp_ms,
+                record.board_id,
+                record.serial_port,
+                record.prediction_label,
+                record.prediction_count as i64,
+                serde_json::to_string(&record.readings)?,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn load_session(&self, session_id: &str) -> anyhow::Result<Vec<SessionRecord>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT timestamp_ms, board_id, serial_port, prediction_label,
+                    prediction_count, readings_json
+             FROM readings WHERE session_id = ?1 ORDER BY timestamp_ms ASC",
+        )?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok(SessionRecord {
+                timestamp_ms: row.get(0)?,
+                board_id: row.get(1)?,
+                serial_port: row.get(2)?,
+                prediction_label: row.get(3)?,
+                prediction_count: row.get::<_, i64>(4)? as usize,
+                readings: serde_json::from_str(&row.get::<_, String>(5)?).unwrap_or_default(),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    fn list_sessions(&self) -> anyhow::Result<Vec<String>> {
+        let conn = self.conn()?;
+        let mut stmt =
+            conn.prepare("SELECT DISTINCT session_id FROM readings ORDER BY session_id ASC")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
+
+/// One session per `<root>/<session_id>.jsonl` file, one record per line.
+pub(crate) struct JsonlStore {
+    root: PathBuf,
+}
+
+impl JsonlStore {
+    pub(crate) fn new(root: impl Into<PathBuf>) -> Self {
+        JsonlStore { root: root.into() }
+    }
+
+    fn session_path(&self, session_id: &str) -> PathBuf {
+        self.root.join(format!("{session_id}.jsonl"))
+    }
+}
+
+impl SessionStore for JsonlStore {
+    fn record(&self, session_id: &str, record: &SessionRecord) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.root)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.session_path(session_id))?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    fn load_session(&self, session_id: &str) -> anyhow::Result<Vec<SessionRecord>> {
+        let contents = fs::read_to_string(self.session_path(session_id))?;
+        contents
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_str(line).map_err(Into::into))
+            .collect()
+    }
+
+    fn list_sessions(&self) -> anyhow::Result<Vec<String>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut sessions: Vec<String> = fs::read_dir(&self.root)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("jsonl"))
+            .filter_map(|entry| {
+                entry
+                    .path()
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(String::from)
+            })
+            .collect();
+        sessions.sort();
+        Ok(sessions)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::Message;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+/// One parsed Tello state broadcast: the comma/semicolon-delimited
+/// `key:value` datagram the drone pushes continuously once it enters
+/// command mode.
+#[derive(Debug, Clone)]
+pub(crate) struct TelemetryState {
+    pub(crate) battery_percent: Option<u8>,
+    pub(crate) height_cm: Option<i32>,
+    pub(crate) time_of_flight_cm: Option<i32>,
+    pub(crate) pitch_deg: Option<i32>,
+    pub(crate) roll_deg: Option<i32>,
+    pub(crate) yaw_deg: Option<i32>,
+    pub(crate) temperature_low_c: Option<i32>,
+    pub(crate) temperature_high_c: Option<i32>,
+    pub(crate) flight_time_s: Option<i32>,
+    received_at: Instant,
+}
+
+impl TelemetryState {
+    fn parse(raw: &str) -> Option<Self> {
+        let fields: HashMap<&str, &str> = raw
+            .trim()
+            .trim_end_matches([';', ','])
+            .split([';', ','])
+            .filter_map(|pair| pair.split_once(':'))
+            .collect();
+
+        if fields.is_empty() {
+            return None;
+        }
+
+        let field = |key: &str| fields.get(key).and_then(|v| v.parse::<i32>().ok());
+
+        Some(TelemetryState {
+            battery_percent: field("bat").map(|v| v as u8),
+            height_cm: field("h"),
+            time_of_flight_cm: field("tof"),
+            pitch_deg: field("pitch"),
+            roll_deg: field("roll"),
+            yaw_deg: field("yaw"),
+            temperature_low_c: field("templ"),
+            temperature_high_c: field("temph"),
+            flight_time_s: field("time"),
+            received_at: Instant::now(),
+        })
+    }
+
+    pub(crate) fn is_stale(&self, timeout: Duration) -> bool {
+        self.received_at.elapsed() > timeout
+    }
+}
+
+const TELLO_STATE_PORT: &str = "0.0.0.0:8890";
+
+/// Listens for the Tello's UDP state broadcast and feeds each parsed packet
+/// back as a `Message::TelemetryUpdate`.
+pub(crate) fn telemetry_subscription() -> iced::Subscription<Message> {
+    iced::subscription::unfold("drone-telemetry", None::<UdpSocket>, |socket| async move {
+        let socket = match socket {
+            Some(socket) => socket,
+            None => match UdpSocket::bind(TELLO_STATE_PORT).await {
+                Ok(socket) => socket,
+                Err(err) => {
+                    // Avoid spinning if the port is already bound.
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    return (Message::TelemetryUpdate(Err(err.to_string())), None);
+                }
+            },
+        };
+
+        let mut buf = [0u8; 256];
+        match socket.recv_from(&mut buf).await {
+            Ok((len, _addr)) => {
+                let raw = String::from_utf8_lossy(&buf[..len]);
+                match TelemetryState::parse(&raw) {
+                    Some(state) => (Message::TelemetryUpdate(Ok(state)), Some(socket)),
+                    None => (
+                        Message::TelemetryUpdate(Err("unparseable telemetry packet".to_string())),
+                        Some(socket),
+                    ),
+                }
+            }
+            Err(err) => (Message::TelemetryUpdate(Err(err.to_string())), Some(socket)),
+        }
+    })
+}